@@ -31,6 +31,25 @@ use vecmath::traits::{ Float, Radians };
 
 use Camera;
 
+/// Selects how movement actions are translated into world-space motion.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum MovementMode {
+    /// Forward/backward movement is locked to the horizontal plane, and
+    /// `FLY_UP`/`FLY_DOWN` move along world Y.
+    ///
+    /// This is the classic walking FPS camera behavior.
+    Grounded,
+    /// Forward/backward movement follows the full view direction (yaw and
+    /// pitch), and `FLY_UP`/`FLY_DOWN` move along the camera's local up.
+    ///
+    /// This is noclip/spectator behavior, useful for free-flying cameras.
+    FreeFly,
+}
+
+impl Default for MovementMode {
+    fn default() -> MovementMode { MovementMode::Grounded }
+}
+
 bitflags!(pub struct Actions: u8 {
     const MOVE_FORWARD  = 0b00000001;
     const MOVE_BACKWARD = 0b00000010;
@@ -43,13 +62,16 @@ bitflags!(pub struct Actions: u8 {
 
 /// First person camera settings.
 pub struct FirstPersonSettings<T=f32> {
-    /// The horizontal movement speed.
+    /// The horizontal thrust multiplier.
     ///
-    /// This is measured in units per second.
+    /// This scales the horizontal thrust direction before it is combined
+    /// with `thrust_mag` to produce acceleration, so it no longer sets a
+    /// literal speed in units per second now that movement has momentum
+    /// (see `thrust_mag`/`friction`/`half_life`).
     pub speed_horizontal: T,
-    /// The vertical movement speed.
+    /// The vertical thrust multiplier.
     ///
-    /// This is measured in units per second.
+    /// Same role as `speed_horizontal`, but for `FLY_UP`/`FLY_DOWN` thrust.
     pub speed_vertical: T,
     /// The horizontal mouse sensitivity.
     ///
@@ -59,6 +81,22 @@ pub struct FirstPersonSettings<T=f32> {
     ///
     /// This is a multiplier applied to vertical mouse movements.
     pub mouse_sensitivity_vertical: T,
+    /// The linear acceleration applied to the velocity while a movement action is held.
+    ///
+    /// This is measured in units per second squared.
+    pub thrust_mag: T,
+    /// The constant deceleration applied toward zero velocity.
+    ///
+    /// This is on top of the exponential drag from `half_life`, and is what
+    /// brings the camera to a complete stop instead of just coasting forever.
+    pub friction: T,
+    /// The time it takes the velocity to decay to half its value from drag alone.
+    ///
+    /// This is measured in seconds. Lower values feel heavier/more damped.
+    pub half_life: T,
+    /// The multiplier applied to `speed_horizontal` and `speed_vertical` while
+    /// `Actions::MOVE_FASTER` is held.
+    pub speed_multiplier_fast: T,
 }
 
 impl<T> Default for FirstPersonSettings<T>
@@ -71,6 +109,10 @@ impl<T> Default for FirstPersonSettings<T>
             speed_vertical: T::one(),
             mouse_sensitivity_horizontal: T::one(),
             mouse_sensitivity_vertical: T::one(),
+            thrust_mag: T::from_isize(20),
+            friction: T::from_isize(2),
+            half_life: T::one() / T::from_isize(5),
+            speed_multiplier_fast: T::from_isize(2),
         }
     }
 }
@@ -85,10 +127,12 @@ pub struct FirstPerson<T=f32> {
     pub pitch: T,
     /// The position of the camera.
     pub position: [T; 3],
-    /// The velocity we are moving.
-    pub velocity: T,
+    /// The current velocity of the camera, in units per second.
+    pub velocity: [T; 3],
     /// The active actions.
     pub actions: Actions,
+    /// How movement actions are translated into world-space motion.
+    pub movement_mode: MovementMode,
 }
 
 impl<T> FirstPerson<T>
@@ -105,29 +149,119 @@ impl<T> FirstPerson<T>
             yaw: _0,
             pitch: _0,
             position: position,
-            velocity: T::one(),
+            velocity: [_0, _0, _0],
             actions: Actions::empty(),
+            movement_mode: MovementMode::default(),
         }
     }
 
     /// Computes camera.
     pub fn camera(&self, dt: T) -> Camera<T> {
-        let dh = dt * self.velocity * self.settings.speed_horizontal;
-        let (dx, dy, dz) = self.movement_direction();
-        let (s, c) = (self.yaw.sin(), self.yaw.cos());
-        let mut camera = Camera::new([
-            self.position[0] + (s * dx - c * dz) * dh,
-            self.position[1] + dy * dt * self.settings.speed_vertical,
-            self.position[2] + (s * dz + c * dx) * dh
-        ]);
+        let (position, _) = self.integrate(dt);
+        let mut camera = Camera::new(position);
         camera.set_yaw_pitch(self.yaw, self.pitch);
         camera
     }
 
     /// Updates the camera for an elapsed number of seconds.
     pub fn update(&mut self, dt: T) {
-        let cam = self.camera(dt);
-        self.position = cam.position;
+        let (position, velocity) = self.integrate(dt);
+        self.position = position;
+        self.velocity = velocity;
+    }
+
+    /// Integrates the flight model forward by `dt` seconds, returning the
+    /// resulting position and velocity without mutating `self`.
+    ///
+    /// Input actions accelerate the velocity, which is then damped by an
+    /// exponential drag (`half_life`) plus a constant friction brake, so
+    /// movement has momentum instead of teleporting directly with input.
+    fn integrate(&self, dt: T) -> ([T; 3], [T; 3]) {
+        let _0: T = T::zero();
+        let _1: T = T::one();
+        let _2 = _1 + _1;
+
+        let thrust = self.thrust_vector();
+
+        let mut velocity = self.velocity;
+        let decay = (_1 / _2).powf(dt / self.settings.half_life);
+        let friction = self.settings.friction * dt;
+        let epsilon = _1 / T::from_isize(1_000_000);
+        for i in 0..3 {
+            velocity[i] = (velocity[i] + thrust[i] * self.settings.thrust_mag * dt) * decay;
+
+            velocity[i] = if velocity[i] > _0 {
+                (velocity[i] - friction).max(_0)
+            } else {
+                (velocity[i] + friction).min(_0)
+            };
+
+            if velocity[i] < epsilon && velocity[i] > -epsilon {
+                velocity[i] = _0;
+            }
+        }
+
+        let position = [
+            self.position[0] + velocity[0] * dt,
+            self.position[1] + velocity[1] * dt,
+            self.position[2] + velocity[2] * dt,
+        ];
+
+        (position, velocity)
+    }
+
+    /// Computes the world-space thrust direction for the current input and
+    /// `movement_mode`, scaled by the configured speeds.
+    fn thrust_vector(&self) -> [T; 3] {
+        let (mut dx, dy, mut dz) = self.movement_direction();
+
+        // Normalize the horizontal pair so diagonal movement isn't faster
+        // than moving along a single axis. Vertical stays independent.
+        let _1 = T::one();
+        let horizontal_len_sq = dx * dx + dz * dz;
+        if horizontal_len_sq > _1 {
+            let inv_len = _1 / horizontal_len_sq.sqrt();
+            dx = dx * inv_len;
+            dz = dz * inv_len;
+        }
+
+        let (s, c) = (self.yaw.sin(), self.yaw.cos());
+
+        let mut speed_horizontal = self.settings.speed_horizontal;
+        let mut speed_vertical = self.settings.speed_vertical;
+        if self.actions.contains(Actions::MOVE_FASTER) {
+            speed_horizontal = speed_horizontal * self.settings.speed_multiplier_fast;
+            speed_vertical = speed_vertical * self.settings.speed_multiplier_fast;
+        }
+
+        // Strafing always stays in the horizontal plane.
+        let strafe_x = s * dx * speed_horizontal;
+        let strafe_z = c * dx * speed_horizontal;
+
+        match self.movement_mode {
+            MovementMode::Grounded => [
+                strafe_x - c * dz * speed_horizontal,
+                dy * speed_vertical,
+                strafe_z + s * dz * speed_horizontal,
+            ],
+            MovementMode::FreeFly => {
+                let (sp, cp) = (self.pitch.sin(), self.pitch.cos());
+                // Forward follows the full view direction (yaw and pitch).
+                let forward = [-c * cp, sp, s * cp];
+                // Local up tilts with pitch instead of staying world-Y.
+                let up = [c * sp, cp, -s * sp];
+                [
+                    strafe_x
+                        + forward[0] * dz * speed_horizontal
+                        + up[0] * dy * speed_vertical,
+                    forward[1] * dz * speed_horizontal
+                        + up[1] * dy * speed_vertical,
+                    strafe_z
+                        + forward[2] * dz * speed_horizontal
+                        + up[2] * dy * speed_vertical,
+                ]
+            }
+        }
     }
 
     /// Updates the camera for a mouse movement.
@@ -152,6 +286,41 @@ impl<T> FirstPerson<T>
         *pitch = (*pitch).min(pi / _2).max(-pi / _2);
     }
 
+    /// Orients the camera to face a given world-space point.
+    ///
+    /// Leaves `yaw`/`pitch` unchanged if `target` coincides with `position`.
+    pub fn look_at(&mut self, target: [T; 3]) {
+        let direction = [
+            target[0] - self.position[0],
+            target[1] - self.position[1],
+            target[2] - self.position[2],
+        ];
+        self.set_direction(direction);
+    }
+
+    /// Orients the camera to face a given world-space direction.
+    ///
+    /// Leaves `yaw`/`pitch` unchanged if `dir` has zero length.
+    pub fn set_direction(&mut self, dir: [T; 3]) {
+        let _0 = T::zero();
+        if dir[0] == _0 && dir[1] == _0 && dir[2] == _0 {
+            return;
+        }
+
+        let pi: T = Radians::_180();
+        let _1 = T::one();
+        let _2 = _1 + _1;
+
+        let horizontal = (dir[0] * dir[0] + dir[2] * dir[2]).sqrt();
+        // Looking straight up/down leaves no horizontal component to derive
+        // a yaw from; keep the current yaw rather than letting atan2's
+        // signed-zero behavior spin it to an arbitrary 0 or pi.
+        if dir[0] != _0 || dir[2] != _0 {
+            self.yaw = dir[2].atan2(-dir[0]);
+        }
+        self.pitch = dir[1].atan2(horizontal).min(pi / _2).max(-pi / _2);
+    }
+
     /// Gets the direction of movement.
     pub fn movement_direction(&self) -> (T, T, T) {
         let (mut dx, mut dy, mut dz) = (T::zero(), T::zero(), T::zero());
@@ -182,3 +351,85 @@ impl<T> FirstPerson<T>
         self.actions &= !action;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn look_at_yaw_matches_grounded_forward_direction() {
+        let mut camera = FirstPerson::new([0.0_f32, 0.0, 0.0], FirstPersonSettings::default());
+        let target = [3.0_f32, 0.0, 4.0];
+
+        camera.look_at(target);
+
+        // The grounded forward direction used by `thrust_vector` (dz = 1, dx = 0).
+        let (s, c) = (camera.yaw.sin(), camera.yaw.cos());
+        let forward = [-c, s];
+
+        let len = (target[0] * target[0] + target[2] * target[2]).sqrt();
+        let expected = [target[0] / len, target[2] / len];
+
+        assert!((forward[0] - expected[0]).abs() < 1e-6);
+        assert!((forward[1] - expected[1]).abs() < 1e-6);
+    }
+
+    #[test]
+    fn look_at_straight_up_preserves_yaw() {
+        let mut camera = FirstPerson::new([0.0_f32, 0.0, 0.0], FirstPersonSettings::default());
+        camera.yaw = 1.2345;
+
+        camera.look_at([0.0, 5.0, 0.0]);
+
+        assert_eq!(camera.yaw, 1.2345);
+        assert!((camera.pitch - ::std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn integrate_snaps_tiny_velocity_to_zero() {
+        let mut camera = FirstPerson::new([0.0_f32, 0.0, 0.0], FirstPersonSettings::default());
+        camera.velocity = [3e-7, -3e-7, 0.0];
+
+        camera.update(0.0);
+
+        assert_eq!(camera.velocity, [0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn free_fly_forward_follows_pitch() {
+        let mut camera = FirstPerson::new([0.0_f32, 0.0, 0.0], FirstPersonSettings::default());
+        camera.movement_mode = MovementMode::FreeFly;
+        camera.pitch = ::std::f32::consts::FRAC_PI_4;
+        camera.enable_actions(Actions::MOVE_FORWARD);
+
+        let thrust = camera.thrust_vector();
+
+        // Looking up 45 degrees, forward thrust should gain a vertical
+        // component instead of staying locked to the horizontal plane.
+        assert!(thrust[1] > 0.1);
+    }
+
+    #[test]
+    fn thrust_vector_normalizes_diagonal_horizontal_input() {
+        let mut camera = FirstPerson::new([0.0_f32, 0.0, 0.0], FirstPersonSettings::default());
+        camera.enable_actions(Actions::MOVE_FORWARD | Actions::STRAFE_LEFT);
+
+        let thrust = camera.thrust_vector();
+        let horizontal_len = (thrust[0] * thrust[0] + thrust[2] * thrust[2]).sqrt();
+
+        assert!((horizontal_len - camera.settings.speed_horizontal).abs() < 1e-6);
+    }
+
+    #[test]
+    fn move_faster_scales_thrust_by_speed_multiplier_fast() {
+        let mut camera = FirstPerson::new([0.0_f32, 0.0, 0.0], FirstPersonSettings::default());
+        camera.enable_actions(Actions::MOVE_FORWARD);
+        let normal = camera.thrust_vector();
+
+        camera.enable_actions(Actions::MOVE_FASTER);
+        let fast = camera.thrust_vector();
+
+        let multiplier = camera.settings.speed_multiplier_fast;
+        assert!((fast[0] - normal[0] * multiplier).abs() < 1e-6);
+    }
+}